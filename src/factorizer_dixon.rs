@@ -22,7 +22,7 @@ use num::{Zero, One, Integer};
 use factorize;
 use factorization::Factorization;
 use factorizer::Factorizer;
-use util::{isqrt,gcd};
+use util::{nth_root_exact,gcd};
 
 pub struct DixonFactorizer<T>
  where T: Eq + Clone + Zero + One + Integer + Hash<Hasher>
@@ -58,8 +58,14 @@ for DixonFactorizer<T>
 	{
 		// Step 1: Collect congruences of the form a^2 = b (mod x), where b < x
 		//          and b is smooth (composed only of small primes).
-		let a_min = isqrt(x.clone()); // XXX: ceil? (ensure a^2 > x)
-
+		// floor(sqrt(x)) + 1: whether or not x is itself a perfect square, this is
+		//  the smallest value whose square is guaranteed to exceed x.
+		let (floor_sqrt, _) = nth_root_exact(x.clone(), 2);
+		let a_min = floor_sqrt + One::one();
+
+		// `extra_count` exists precisely so that #relations > #primes; this forces the
+		//  GF(2) exponent matrix built below to be rank-deficient, guaranteeing at least
+		//  one nonzero vector in its left null space.
 		let a_count = self.primes.len() + self.extra_count;
 		let mut a_values: Vec<T> = Vec::new();
 		let mut b_factorizations: Vec<Factorization<T>> = Vec::new();
@@ -86,10 +92,23 @@ for DixonFactorizer<T>
 				// Try to factorize b using only small primes
 				let b_factorization = factorize_limited(b, &self.primes);
 
-				if b_factorization.is_some() {
+				if let Some(b_factorization) = b_factorization {
+
+					// A relation whose `b` is already a perfect square is a dependency
+					//  all by itself (its parity vector is the zero vector), so there's
+					//  no need to wait for the linear algebra below to rediscover it.
+					if is_square_factorization(&b_factorization, &self.primes) {
+						let b_sqrt = b_factorization.sqrt().unwrap().product();
+						let candidate = gcd(a.clone() - b_sqrt, x.clone());
+
+						if candidate != One::one() && candidate != x.clone() {
+							return candidate;
+						}
+					}
+
 					// Record it and reset the attempt counter
 					a_values.push(a);
-					b_factorizations.push(b_factorization.unwrap());
+					b_factorizations.push(b_factorization);
 					continue 'a;
 				}
 			}
@@ -99,44 +118,56 @@ for DixonFactorizer<T>
 		assert_eq!(a_values.len(), a_count);
 		assert_eq!(b_factorizations.len(), a_count);
 
-		// Step 2: Find products of b's which are square.
-		// NOTE: not currently a big fan of how this is accomplished. (using linear algebra, etc)
-		//       This problem is isomorphic to a rather simple problem in combinatorial
+		// Step 2: Find products of b's which are square, i.e. vectors in the left null
+		//          space of the (prime exponent mod 2) matrix.
+		// NOTE: this problem is isomorphic to a rather simple problem in combinatorial
 		//       game theory (given a set of impartial games with known nimbers, find
-		//       subsets with nimsum 0), and the solution here feels unintuitive and
-		//       convoluted in comparison.
-		//       (it also generates much fewer results... but perhaps many of the additional
-		//        sums generated by the CGT solution are not useful here)
+		//       subsets with nimsum 0); see the `games` module for that connection made
+		//       explicit ("Very Limited Xor Subset": enumerating subsets with xor-sum
+		//       zero).
 
 		// Use a bit array to represent each b's factorization mod 2
 		let mut bitmatrix = bit_matrix_from_factorizations(&b_factorizations, &self.primes);
 
-		// Put in row echelon form
+		// Reduce to row echelon form.  Every row that ends up all-zero is a basis vector
+		//  of the null space: its `indices` set names a subset of relations whose b's
+		//  multiply together to form a perfect square.
 		bit_matrix_to_ref(&mut bitmatrix);
 
-		// Each row full of zeros in the matrix represents a set of b values that multiply
-		//  together to form a square.
-		for matrix_row in bitmatrix.into_rows().into_iter() {
-
-			if matrix_row.is_all_zero() {
-
-				let mut a_prod: T = One::one();
-				let mut b_prod_factors: Factorization<T> = One::one();
+		let basis: Vec<BitSet> = bitmatrix.into_rows().into_iter()
+			.filter(|row| row.is_all_zero())
+			.map(|row| row.into_index_set())
+			.collect();
+
+		// Guaranteed nonempty by the a_count invariant above.
+		assert!(!basis.is_empty());
+
+		// A basis by itself only gives us `dim` candidate congruences, but the *entire*
+		//  null space (all 2^dim - 1 nonzero xor-combinations of the basis) is fair game,
+		//  and often the basis vectors alone yield nothing but trivial gcds.  Try singles
+		//  first, then widen to pairs, then triples, before giving up.
+		for indices in basis.iter() {
+			if let Some(candidate) = try_dependency(indices, x, &a_values, &b_factorizations) {
+				return candidate;
+			}
+		}
 
-				for index in matrix_row.into_index_set().iter() {
-					a_prod = a_prod * a_values[index].clone();
-					b_prod_factors = b_prod_factors * b_factorizations[index].clone();
+		for i in (0usize..basis.len()) {
+			for j in ((i+1)..basis.len()) {
+				let combined = xor_bitsets(&basis[i], &basis[j]);
+				if let Some(candidate) = try_dependency(&combined, x, &a_values, &b_factorizations) {
+					return candidate;
 				}
+			}
+		}
 
-				// we now have a congruence of squares (mod x) between a_prod^2 and b_prod
-				let b_prodsqrt_factors = b_prod_factors.sqrt().unwrap();
-				let b_prodsqrt = b_prodsqrt_factors.product();
-
-				// a - sqrt(b) has a high chance of sharing a nontrivial factor in common with x
-				let candidate = gcd(a_prod - b_prodsqrt,  x.clone());
-
-				if candidate != One::one() && candidate != x.clone() {
-					return candidate;
+		for i in (0usize..basis.len()) {
+			for j in ((i+1)..basis.len()) {
+				for k in ((j+1)..basis.len()) {
+					let combined = xor_bitsets(&xor_bitsets(&basis[i], &basis[j]), &basis[k]);
+					if let Some(candidate) = try_dependency(&combined, x, &a_values, &b_factorizations) {
+						return candidate;
+					}
 				}
 			}
 		}
@@ -146,6 +177,54 @@ for DixonFactorizer<T>
 	}
 }
 
+// Whether a factorization (restricted to the given small primes) is already a perfect
+//  square, i.e. its mod-2 parity vector is the zero vector.
+fn is_square_factorization<T>(fact: &Factorization<T>, primes: &Vec<T>) -> bool
+ where T: Eq + Clone + Zero + One + Integer + Hash<Hasher>
+{
+	primes.iter().all(|p| fact.get(p) % 2 == 0)
+}
+
+// Given a set of relation indices known to multiply together into a perfect square
+//  (mod x), compute gcd(a_prod - sqrt(b_prod), x) and report it if nontrivial.
+fn try_dependency<T>(
+	indices:           &BitSet,
+	x:                 &T,
+	a_values:          &Vec<T>,
+	b_factorizations:  &Vec<Factorization<T>>,
+) -> Option<T>
+ where T: Eq + Clone + Zero + One + Integer + Hash<Hasher>
+{
+	let mut a_prod: T = One::one();
+	let mut b_prod_factors: Factorization<T> = One::one();
+
+	for index in indices.iter() {
+		a_prod = a_prod * a_values[index].clone();
+		b_prod_factors = b_prod_factors * b_factorizations[index].clone();
+	}
+
+	// we now have a congruence of squares (mod x) between a_prod^2 and b_prod
+	let b_prodsqrt_factors = b_prod_factors.sqrt().unwrap();
+	let b_prodsqrt = b_prodsqrt_factors.product();
+
+	// a - sqrt(b) has a high chance of sharing a nontrivial factor in common with x
+	let candidate = gcd(a_prod - b_prodsqrt, x.clone());
+
+	if candidate != One::one() && candidate != x.clone() {
+		Some(candidate)
+	} else {
+		None
+	}
+}
+
+// Xor (symmetric difference) of two index sets, used to combine null-space basis
+//  vectors into additional square congruences.
+fn xor_bitsets(a: &BitSet, b: &BitSet) -> BitSet {
+	let mut out = a.clone();
+	out.symmetric_difference_with(b);
+	out
+}
+
 
 
 // Utility function that only returns a factorization if it can be constructed
@@ -30,6 +30,7 @@ use factorizer::Factorizer;
 use util::literal;
 use util::gcd;
 use util::mod_pow;
+use montgomery::Montgomery;
 
 pub struct PollardBrentFactorizer<T>;
 
@@ -49,9 +50,15 @@ for PollardBrentFactorizer<T>
 		if x.is_multiple_of(&literal(3)) { return literal(3); }
 		if x < &literal(2) { return x.clone(); }
 
+		// x is odd at this point (the is_even check above already handled 2, the only
+		//  even prime), so the whole iteration below can run in Montgomery form: every
+		//  `y := y^2 + c (mod x)` step and every `q := q*(z-y) (mod x)` update becomes a
+		//  multiply plus a shift-and-add instead of a full division.
+		let mont = Montgomery::new(x.clone());
+
 		let mut rng = weak_rng();
-		let mut y: T = rng.gen_range(One::one(), x.clone()); // current value in the sequence:  y := y^2 + c (mod n)
-		let mut c: T = rng.gen_range(One::one(), x.clone()); // parameter of y sequence
+		let mut y: T = mont.to_montgomery(rng.gen_range(One::one(), x.clone())); // current value in the sequence:  y := y^2 + c (mod n)
+		let c: T = mont.to_montgomery(rng.gen_range(One::one(), x.clone())); // parameter of y sequence
 		let mut m: T = rng.gen_range(One::one(), x.clone()); // step size when multiplying crap together
 
 		let mut g: T = One::one(); // contains the result
@@ -66,7 +73,7 @@ for PollardBrentFactorizer<T>
 			z = y.clone();
 
 			for _ in num::iter::range(Zero::zero(), r) {
-				y = next_in_sequence(y, x.clone(), c.clone());
+				y = next_in_sequence_montgomery(y, &mont, c.clone(), x.clone());
 			}
 
 			let mut k: T = Zero::zero();
@@ -77,14 +84,22 @@ for PollardBrentFactorizer<T>
 
 				// Multiply a bunch of (z-y) terms together (which may share factors with x)
 				for _ in num::iter::range(Zero::zero(), niter) {
-					y = next_in_sequence(y, x.clone(), c.clone());
+					y = next_in_sequence_montgomery(y, &mont, c.clone(), x.clone());
 
 					// Deviation from the source linked above, to support unsigned integers:
 					//    abs(z-y) % x  --->  (x+z-y) % x
 					// This is based on the notion that `gcd(+a % b, b) == gcd(-a % b, b)`,
-					// so the absolute value isn't really necessary.
-					q = q * (x.clone() + z.clone() - y.clone());
-					q = q % x.clone();
+					// so the absolute value isn't really necessary.  `z` and `y` are
+					// Montgomery-form, so `diff` is too (scaled by one factor of `r`);
+					// but `q` itself is NOT converted to Montgomery form, and must stay
+					// that way.  `mont.mul` divides out one factor of `r` per call, which
+					// exactly cancels the one factor `diff` carries each time, so `q`
+					// comes out as the plain running product of `(z-y)` terms, unscaled,
+					// at every step -- not the Montgomery form of it.  (Seeding `q` with
+					// `mont.to_montgomery(1)` instead of plain `1` would scale the result
+					// by an extra factor of `r` and break the `gcd(x, q)` below.)
+					let diff = sub_mod(x.clone(), z.clone(), y.clone());
+					q = mont.mul(q, diff);
 				}
 
 				g = gcd(x.clone(), q.clone());
@@ -102,8 +117,9 @@ for PollardBrentFactorizer<T>
 
 			loop {
 				// Do a more fine grained search (computing the GCD every step)
-				y = next_in_sequence(y, x.clone(), c.clone());
-				g = gcd(x.clone(), x.clone() + z.clone() - y.clone()); // same deviation as noted above
+				y = next_in_sequence_montgomery(y, &mont, c.clone(), x.clone());
+				let diff = sub_mod(x.clone(), z.clone(), y.clone());
+				g = gcd(x.clone(), diff); // same deviation as noted above
 
 				if g > One::one() { break; }
 			}
@@ -115,13 +131,23 @@ for PollardBrentFactorizer<T>
 	}
 }
 
-// computes (y**2 + c) % x
-fn next_in_sequence<T>(y: T, x: T, c: T) -> T
-	where T: Clone + Integer,
+// computes (y**2 + c) % x, with the squaring done in Montgomery form so that it
+//  costs a multiply and a shift-and-add rather than a full division.  `mont.square(y)`
+//  and `c` are both already in [0, x), so their sum is in [0, 2x) and a single
+//  conditional subtract (no division) brings it back into range.
+fn next_in_sequence_montgomery<T>(y: T, mont: &Montgomery<T>, c: T, x: T) -> T
+	where T: Clone + Zero + One + Integer + Shr<usize, Output=T>,
+{
+	let s = mont.square(y) + c;
+	if s >= x { s - x } else { s }
+}
+
+// computes (x + z - y) % x without a division: `z` and `y` are both already in
+//  [0, x), so `x + z - y` is in [1, 2x), and a single conditional subtract
+//  brings it back into range.
+fn sub_mod<T>(x: T, z: T, y: T) -> T
+	where T: Clone + Zero + One + Integer,
 {
-	let mut result = y.clone() * y;
-	result = result % x.clone();
-	result = result + c;
-	result = result % x;
-	return result;
+	let s = x.clone() + z - y;
+	if s >= x { s - x } else { s }
 }
\ No newline at end of file
@@ -0,0 +1,120 @@
+// Copyright 2015 Michael 'ExpHP' Lamparski
+//
+// Licensed under the terms of the MIT License, available at:
+//  http://opensource.org/licenses/MIT
+// and also included in the file COPYING at the root of this distribution.
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+
+//! Combinatorial game theory built on top of `Factors`.
+//!
+//! The "find a subset of relations whose b's multiply to a square" step used by
+//! `DixonFactorizer` (see the NOTE in `factorizer_dixon`) is isomorphic to the
+//! standard impartial "prime factor game": a position is a multiset of positive
+//! integers, and a move replaces one number by one of its proper divisors
+//! (equivalently, decrements the exponent of one prime in its factorization).
+//!
+//! This is just Nim in disguise.  A single prime power `p^r` is itself a game
+//! equivalent to a Nim-heap of size `r` (the only moves are to `p^0, .., p^(r-1)`,
+//! exactly like a Nim-heap of `r` may be reduced to any smaller heap), so by the
+//! Sprague-Grundy theorem the Grundy value of a whole multiset is the nim-sum (xor)
+//! of every prime's exponent, across every number in the multiset.  The first
+//! player to move wins iff that nim-sum is nonzero.
+
+use num::{Zero, One, Integer};
+
+use factors::Factors;
+
+/// The Grundy (Sprague-Grundy) value of a single prime-factor game position.
+///
+/// Under the standard rule (any exponent may be reduced to any smaller one in a
+/// single move, exactly like a Nim-heap), the Grundy value of `p^r` is `r`, so the
+/// value of a whole `Factors` is the nim-sum (xor) of the exponents of every prime
+/// appearing in it.
+pub trait GrundyValue {
+	/// Grundy value under the standard prime-factor game rule.
+	fn grundy_value(self: &Self) -> u64;
+
+	/// Grundy value under a "subtraction game" variant where an exponent `r` may
+	/// only be reduced by strictly less than `m` in one move (`m == 1` never allows
+	/// a move; the standard rule above is the limit as `m` grows past every
+	/// exponent).  The per-prime Grundy value in that variant is `r % m`.
+	fn grundy_value_mod(self: &Self, m: u64) -> u64;
+}
+
+impl<T> GrundyValue for Factors<T>
+ where T: Eq + Clone + Zero + One + Integer
+{
+	fn grundy_value(self: &Self) -> u64
+	{
+		self.iter().fold(0u64, |acc, (_, exponent)| acc ^ (exponent as u64))
+	}
+
+	fn grundy_value_mod(self: &Self, m: u64) -> u64
+	{
+		assert!(m > 0);
+		self.iter().fold(0u64, |acc, (_, exponent)| acc ^ (exponent as u64 % m))
+	}
+}
+
+/// Nim-sum of the Grundy values of several factorizations, i.e. the Grundy value
+/// of the combined game formed by playing all of them side-by-side.
+///
+/// The first player to move in the combined game wins iff the result is nonzero.
+pub fn nim_sum<T>(games: &[Factors<T>]) -> u64
+ where T: Eq + Clone + Zero + One + Integer
+{
+	games.iter().fold(0u64, |acc, game| acc ^ game.grundy_value())
+}
+
+/// Like `nim_sum`, but under the `m`-bounded subtraction-game variant (see
+/// `GrundyValue::grundy_value_mod`).
+pub fn nim_sum_mod<T>(games: &[Factors<T>], m: u64) -> u64
+ where T: Eq + Clone + Zero + One + Integer
+{
+	games.iter().fold(0u64, |acc, game| acc ^ game.grundy_value_mod(m))
+}
+
+/// Whether the first player to move has a winning strategy in the combined game
+/// formed by playing every `Factors` in `games` side-by-side.
+pub fn first_player_wins<T>(games: &[Factors<T>]) -> bool
+ where T: Eq + Clone + Zero + One + Integer
+{
+	nim_sum(games) != 0
+}
+
+#[test]
+fn test_grundy_value() {
+	use factorize;
+
+	// 8 = 2^3: a single pile, Grundy value equal to the exponent.
+	assert_eq!(factorize(8usize).grundy_value(), 3);
+
+	// 180 = 2^2 * 3^2 * 5^1: nim-sum of the exponents, 2 ^ 2 ^ 1 == 1.
+	assert_eq!(factorize(180usize).grundy_value(), 1);
+}
+
+#[test]
+fn test_nim_sum_and_first_player_wins() {
+	use factorize;
+
+	// 8 = 2^3 and 4 = 2^2: nim-sum of single-pile values 3 and 2 is 1 (nonzero).
+	let games = [factorize(8usize), factorize(4usize)];
+	assert_eq!(nim_sum(&games), 3 ^ 2);
+	assert!(first_player_wins(&games));
+
+	// Two copies of the same position always cancel under xor: second player wins.
+	let mirrored = [factorize(12usize), factorize(12usize)];
+	assert_eq!(nim_sum(&mirrored), 0);
+	assert!(!first_player_wins(&mirrored));
+}
+
+#[test]
+fn test_grundy_value_mod() {
+	use factorize;
+
+	// Under the standard rule 12 = 2^2 * 3^1 has grundy value 2^1 == 3.
+	// Bounding exponents mod 2 instead gives (2%2) ^ (1%2) == 0 ^ 1 == 1.
+	assert_eq!(factorize(12usize).grundy_value(), 3);
+	assert_eq!(factorize(12usize).grundy_value_mod(2), 1);
+}
@@ -0,0 +1,213 @@
+// Copyright 2015 Michael 'ExpHP' Lamparski
+//
+// Licensed under the terms of the MIT License, available at:
+//  http://opensource.org/licenses/MIT
+// and also included in the file COPYING at the root of this distribution.
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+
+//! Gaussian integers (`Z[i]`), the first concrete `UfdElement` domain.
+//!
+//! `Z[i]` is a Euclidean domain under the norm `N(a+bi) = a^2+b^2`, with four
+//! units (`1`, `i`, `-1`, `-i`).  Factoring a `GaussianInt` therefore needs a
+//! convention for picking one associate out of each group of four as "the"
+//! prime; `normalize` picks the one lying in the closed first quadrant
+//! (`re > 0 && im >= 0`) — exactly one of any four associates lands there,
+//! since each multiplication by `i` is a 90-degree rotation.
+
+use std::ops::{Add,Sub,Mul,Neg};
+
+use ufd::{EuclideanDomain,UfdElement,UfdFactorization,trial_divide};
+use primes_upto;
+use util::isqrt;
+
+/// An element `re + im*i` of `Z[i]`.
+#[derive(Eq,PartialEq,Clone,Copy,Hash,Debug)]
+pub struct GaussianInt {
+	pub re: i64,
+	pub im: i64,
+}
+
+impl GaussianInt {
+	pub fn new(re: i64, im: i64) -> Self { GaussianInt { re: re, im: im } }
+
+	fn conj(self: &Self) -> Self { GaussianInt::new(self.re, -self.im) }
+}
+
+impl Add for GaussianInt {
+	type Output = Self;
+	fn add(self, other: Self) -> Self { GaussianInt::new(self.re + other.re, self.im + other.im) }
+}
+
+impl Sub for GaussianInt {
+	type Output = Self;
+	fn sub(self, other: Self) -> Self { GaussianInt::new(self.re - other.re, self.im - other.im) }
+}
+
+impl Mul for GaussianInt {
+	type Output = Self;
+	fn mul(self, other: Self) -> Self
+	{
+		GaussianInt::new(
+			self.re*other.re - self.im*other.im,
+			self.re*other.im + self.im*other.re,
+		)
+	}
+}
+
+impl Neg for GaussianInt {
+	type Output = Self;
+	fn neg(self) -> Self { GaussianInt::new(-self.re, -self.im) }
+}
+
+impl EuclideanDomain for GaussianInt {
+	fn norm(self: &Self) -> u64 { (self.re*self.re + self.im*self.im) as u64 }
+
+	fn zero() -> Self { GaussianInt::new(0, 0) }
+	fn one()  -> Self { GaussianInt::new(1, 0) }
+
+	// Division in Q[i] followed by rounding each component to the nearest
+	//  integer gives a quotient close enough that the remainder's norm is
+	//  strictly smaller than other's, which is all the Euclidean property
+	//  requires (Z[i] doesn't need the *best* quotient, just a good enough one).
+	fn div_rem(self: &Self, other: &Self) -> (Self, Self)
+	{
+		assert!(!other.is_zero());
+
+		let other_norm = other.norm() as i64;
+		let scaled = *self * other.conj(); // self/other, scaled up by other_norm
+
+		let q = GaussianInt::new(
+			round_div(scaled.re, other_norm),
+			round_div(scaled.im, other_norm),
+		);
+		let r = *self - q * *other;
+
+		(q, r)
+	}
+}
+
+impl UfdElement for GaussianInt {
+	fn is_unit(self: &Self) -> bool { self.norm() == 1 }
+
+	// The four quadrants (re>0&&im>=0 / re<=0&&im>0 / re<0&&im<=0 / re>=0&&im<0)
+	//  exactly partition the plane minus the origin, and multiplying by 1, -i,
+	//  -1, i respectively rotates each one into the first (re>0&&im>=0).  Call
+	//  that rotation `rotator`; since it's a unit, its inverse is its conjugate,
+	//  and `unit_part` is defined as that inverse so that `self == unit_part() *
+	//  normalize()` with `normalize() == rotator * self`.
+	fn unit_part(self: &Self) -> Self
+	{
+		if self.is_zero() { return GaussianInt::one(); }
+
+		let rotator =
+			if      self.re >  0 && self.im >= 0 { GaussianInt::new(1, 0) }
+			else if self.re <= 0 && self.im >  0 { GaussianInt::new(0, -1) }
+			else if self.re <  0 && self.im <= 0 { GaussianInt::new(-1, 0) }
+			else                                   { GaussianInt::new(0, 1) };
+
+		rotator.conj()
+	}
+
+	fn normalize(self: &Self) -> Self
+	{
+		if self.is_zero() { return self.clone(); }
+		// unit_part() always has norm 1, so its inverse is its conjugate.
+		*self * self.unit_part().conj()
+	}
+}
+
+// Rounds `n / d` (`d > 0`) to the nearest integer, ties rounding up.
+fn round_div(n: i64, d: i64) -> i64
+{
+	assert!(d > 0);
+
+	let q = n / d;
+	let r = n % d; // same sign as n (or zero), in (-d, d)
+
+	let (q, r) = if r < 0 { (q - 1, r + d) } else { (q, r) }; // r now in [0, d)
+
+	if 2*r >= d { q + 1 } else { q }
+}
+
+/// Factors `n` over `Z[i]`, end-to-end: generates enough Gaussian primes to
+/// cover every possible factor of `n` (see `gaussian_primes_upto`) and hands
+/// them to `ufd::trial_divide`.
+pub fn factorize_gaussian(n: GaussianInt) -> UfdFactorization<GaussianInt>
+{
+	assert!(!n.is_zero());
+
+	// Any Gaussian prime dividing n has norm <= norm(n), and a Gaussian prime's
+	//  norm is either the rational prime it sits over (split case) or that
+	//  prime's square (inert case); either way the underlying rational prime is
+	//  at most norm(n), so sieving rational primes up to norm(n) is a safe
+	//  (if not the tightest possible) bound.
+	let limit = n.norm();
+	let candidate_primes = gaussian_primes_upto(limit);
+
+	trial_divide(n, &candidate_primes)
+}
+
+/// Enumerates the normalized Gaussian primes lying over each rational prime up
+/// to `limit`, by the standard splitting law in `Z[i]`:
+///
+/// - the rational prime `2` ramifies: `2 == -i*(1+i)^2`, so `1+i` is the prime.
+/// - a rational prime `p == 4k+1` splits into two conjugate Gaussian primes of
+///   norm `p`, found by brute-force search for `a^2+b^2 == p`.
+/// - a rational prime `p == 4k+3` stays prime (inert) in `Z[i]`, with norm `p^2`.
+///
+/// Both primes of each splitting pair are returned: the conjugates `a+bi` and
+/// `a-bi` are not associates of each other (e.g. `2+i` and `2-i`), so trial
+/// division needs both.
+fn gaussian_primes_upto(limit: u64) -> Vec<GaussianInt>
+{
+	let rational_primes: Vec<u64> = primes_upto(limit as usize);
+
+	rational_primes.into_iter().flat_map(|p| {
+		let primes_over_p = if p == 2 {
+			vec![GaussianInt::new(1, 1)]
+		} else if p % 4 == 1 {
+			let a = (1u64..p).find(|&a| {
+				let b2 = p - a*a;
+				isqrt(b2) * isqrt(b2) == b2
+			}).expect("every p == 4k+1 is a sum of two squares");
+			let b = isqrt(p - a*a);
+			// a+bi, and b+ai (the normalized associate of its conjugate a-bi)
+			vec![GaussianInt::new(a as i64, b as i64), GaussianInt::new(b as i64, a as i64)]
+		} else {
+			vec![GaussianInt::new(p as i64, 0)]
+		};
+		primes_over_p.into_iter()
+	}).map(|g| g.normalize()).collect()
+}
+
+#[test]
+fn test_gaussian_div_rem() {
+	// (3+4i) = (2+1i)*(2+1i) + (0+0i):  (2+i)^2 = 4 + 4i - 1 = 3 + 4i  -- pick an
+	//  exact case so the test isn't sensitive to rounding.
+	let a = GaussianInt::new(3, 4);
+	let b = GaussianInt::new(2, 1);
+	let (q, r) = a.div_rem(&b);
+	assert_eq!(q, GaussianInt::new(2, 1));
+	assert_eq!(r, GaussianInt::new(0, 0));
+}
+
+#[test]
+fn test_gaussian_normalize_associates() {
+	let p = GaussianInt::new(1, 2);
+	let associates = [p, GaussianInt::new(0,1)*p, GaussianInt::new(-1,0)*p, GaussianInt::new(0,-1)*p];
+	for a in associates.iter() {
+		assert_eq!(a.normalize(), p.normalize());
+	}
+}
+
+#[test]
+fn test_factorize_gaussian() {
+	// 3+4i == (2+i)^2 exactly (both are norm-5 Gaussian primes' square), so the
+	//  generated candidate primes should recover (2+i) with exponent 2, and
+	//  product() should reconstruct 3+4i exactly.
+	let n = GaussianInt::new(3, 4);
+	let f = factorize_gaussian(n);
+	assert_eq!(f.get(&GaussianInt::new(2, 1)), 2);
+	assert_eq!(f.product(), n);
+}
@@ -32,6 +32,10 @@ mod factorizer_sieve;
 mod iter_ext;
 mod util;
 mod factors;
+mod games;
+mod montgomery;
+mod ufd;
+mod gaussian;
 pub mod prelude;
 
 pub use primes::PrimeTester;
@@ -53,8 +57,15 @@ pub use iter_ext::FactorExt;
 pub use util::isqrt;
 pub use util::gcd;
 pub use factors::Factors;
+pub use games::GrundyValue;
+pub use games::nim_sum;
+pub use ufd::EuclideanDomain;
+pub use ufd::UfdElement;
+pub use gaussian::GaussianInt;
+pub use gaussian::factorize_gaussian;
 
 use util::literal;
+use util::perfect_power;
 
 use std::iter::FromIterator;
 use std::ops::Shr;
@@ -63,9 +74,24 @@ use num::{Zero,One,Integer};
 use num::{FromPrimitive,ToPrimitive};
 
 /// Factors a number using `DefaultFactorizer`.
+///
+/// Before handing off to `DefaultFactorizer`, checks whether `x` is itself a
+/// perfect power (`x == base^exp`); if so, `base` is factored once and every
+/// resulting exponent is scaled by `exp`, rather than letting Pollard-Brent or
+/// trial division rediscover the same base over and over.
 pub fn factorize<T>(x: T) -> Factors<T>
  where T: Clone + Zero + One + Integer + Shr<usize, Output=T> + ToPrimitive + FromPrimitive
 {
+	if let Some((base, exp)) = perfect_power(x.clone()) {
+		let base_factors = DefaultFactorizer.factorize(base);
+
+		let mut factors: Factors<T> = One::one();
+		for (p, count) in base_factors.iter() {
+			factors.set(p.clone(), count * exp);
+		}
+		return factors;
+	}
+
 	DefaultFactorizer.factorize(x)
 }
 
@@ -0,0 +1,176 @@
+// Copyright 2015 Michael 'ExpHP' Lamparski
+//
+// Licensed under the terms of the MIT License, available at:
+//  http://opensource.org/licenses/MIT
+// and also included in the file COPYING at the root of this distribution.
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+
+//! Montgomery-form modular arithmetic.
+//!
+//! Plain modular arithmetic (as in `util::mod_pow`) does a full `% x` after every
+//! multiply, and in a tight loop such as `PollardBrentFactorizer`'s sequence
+//! iteration those divisions dominate the runtime.  REDC (Montgomery reduction) replaces that
+//! division with a multiply plus a shift-and-add, at the cost of first converting
+//! in and out of "Montgomery form" (`a` is represented as `a*r mod n` for a fixed
+//! `r = 2^w` coprime to `n`).
+//!
+//! Only odd moduli are supported (REDC requires `gcd(r, n) == 1`, and `r` is a power
+//! of two); callers are expected to special-case even `n` themselves.  The one
+//! caller, `PollardBrentFactorizer::get_factor`, already does: it returns 2 for
+//! an even `x` before building a context, since an even modulus trivially
+//! factors as 2 times something.
+//!
+//! # Width requirement for fixed-width `T`
+//! REDC's intermediates (`t ~= n*r` inside `redc`, and `m*n` building the
+//! reduction) need roughly `2 * bit_length(n)` bits of headroom, not just the
+//! `bit_length(n) + 1` bits `r` itself is sized to.  For an arbitrary-precision
+//! type (`BigInt`) that's free.  For a fixed-width type (`u32`, `u64`, ...) it is
+//! only safe while `n` stays under roughly half the type's width (e.g. a `u64`
+//! modulus above ~2^31 can silently wrap); `Montgomery::new` cannot check this
+//! bound itself, since `num`'s unbounded integer types have no notion of a
+//! maximum width to compare `n` against. Callers passing a fixed-width `T` are
+//! responsible for keeping `n` within that bound.
+
+use std::ops::Shr;
+
+use num::{Zero,One,Integer};
+
+use util::literal;
+
+/// A Montgomery reduction context for a fixed odd modulus `n`.
+pub struct Montgomery<T> {
+	n:           T,       // the modulus
+	word_bits:   usize,   // w, chosen so that n < 2^w
+	modulus_pow: T,       // r = 2^w
+	n_prime:     T,       // -n^-1 mod 2^w
+	r2:          T,       // 2^(2w) mod n, used to enter Montgomery form
+}
+
+impl<T> Montgomery<T>
+ where T: Clone + Zero + One + Integer + Shr<usize, Output=T>
+{
+	/// Build a Montgomery context for the given odd modulus.
+	pub fn new(n: T) -> Self
+	{
+		assert!(n.is_odd(), "Montgomery reduction requires an odd modulus");
+		assert!(n > One::one());
+
+		let word_bits = bit_length(n.clone()) + 1; // +1 margin: strictly n < 2^w
+		let modulus_pow = two_pow(word_bits);
+
+		// Catches only the coarsest possible overflow: `modulus_pow` itself
+		//  wrapping around T's width while being built by repeated doubling
+		//  above. It does NOT guarantee REDC's wider intermediates are safe for
+		//  a fixed-width T -- that requires n to stay under roughly half of T's
+		//  width, which this assert can't see (see "Width requirement" above).
+		assert!(modulus_pow > n, "Montgomery::new: 2^{} overflowed T -- n is too large for T's width (see module docs)", word_bits);
+
+		// r2 = 2^(2w) mod n, by repeated doubling mod n.
+		let mut r2: T = One::one();
+		for _ in (0usize..(2*word_bits)) {
+			r2 = (r2.clone() + r2) % n.clone();
+		}
+
+		// n' = -n^-1 mod 2^w, by Newton iteration on the word: each round doubles
+		//  the number of correct low bits of n^-1 mod 2^(bits_correct).
+		//   inv <- inv * (2 - n*inv)   (mod 2^(2*bits_correct))
+		let mut inv: T = One::one(); // correct mod 2^1, since n is odd
+		let mut bits_correct = 1usize;
+		while bits_correct < word_bits {
+			bits_correct = bits_correct * 2;
+			let step_modulus = two_pow(bits_correct);
+
+			let nx_mod = (n.clone() * inv.clone()) % step_modulus.clone();
+			let term = (step_modulus.clone() + literal(2) - nx_mod) % step_modulus.clone();
+			inv = (inv * term) % step_modulus;
+		}
+		inv = inv % modulus_pow.clone();
+		let n_prime = (modulus_pow.clone() - inv) % modulus_pow.clone();
+
+		Montgomery { n: n, word_bits: word_bits, modulus_pow: modulus_pow, n_prime: n_prime, r2: r2 }
+	}
+
+	/// Converts `a` (an ordinary residue, `0 <= a < n`) into Montgomery form.
+	pub fn to_montgomery(self: &Self, a: T) -> T
+	{
+		self.redc((a % self.n.clone()) * self.r2.clone())
+	}
+
+	/// Converts a Montgomery-form value back into an ordinary residue.
+	pub fn from_montgomery(self: &Self, a: T) -> T
+	{
+		self.redc(a)
+	}
+
+	/// Multiplies two Montgomery-form values, returning a Montgomery-form result.
+	pub fn mul(self: &Self, a: T, b: T) -> T
+	{
+		self.redc(a * b)
+	}
+
+	/// Squares a Montgomery-form value, returning a Montgomery-form result.
+	pub fn square(self: &Self, a: T) -> T
+	{
+		self.mul(a.clone(), a)
+	}
+
+	// REDC(t) = t * r^-1 mod n, for 0 <= t < n*r.  This is the one operation in
+	//  the whole module that replaces a division-heavy `% n` with a multiply and
+	//  a shift-and-add (division by the power-of-two `modulus_pow`).
+	fn redc(self: &Self, t: T) -> T
+	{
+		let m = ((t.clone() % self.modulus_pow.clone()) * self.n_prime.clone()) % self.modulus_pow.clone();
+		let u = (t + m * self.n.clone()) >> self.word_bits;
+
+		if u >= self.n { u - self.n.clone() } else { u }
+	}
+}
+
+fn two_pow<T>(w: usize) -> T
+ where T: Clone + Zero + One
+{
+	let mut x: T = One::one();
+	for _ in (0usize..w) { x = x.clone() + x; }
+	x
+}
+
+fn bit_length<T>(mut n: T) -> usize
+ where T: Clone + Zero + Shr<usize, Output=T> + PartialOrd
+{
+	let mut bits = 0usize;
+	while n > Zero::zero() {
+		n = n >> 1usize;
+		bits += 1;
+	}
+	bits
+}
+
+#[test]
+fn test_to_from_montgomery_roundtrip() {
+	for &n in [3u64, 7, 15, 101, 1000003].iter() {
+		let mont = Montgomery::new(n);
+		for a in (0u64..n) {
+			assert_eq!(mont.from_montgomery(mont.to_montgomery(a)), a);
+		}
+	}
+}
+
+#[test]
+fn test_mul_and_square_match_plain_arithmetic() {
+	for &n in [3u64, 7, 15, 101].iter() {
+		let mont = Montgomery::new(n);
+		for a in (0u64..n) {
+			for b in (0u64..n) {
+				let a_mont = mont.to_montgomery(a);
+				let b_mont = mont.to_montgomery(b);
+
+				let got_mul = mont.from_montgomery(mont.mul(a_mont.clone(), b_mont));
+				assert_eq!(got_mul, (a * b) % n);
+
+				let got_square = mont.from_montgomery(mont.square(a_mont));
+				assert_eq!(got_square, (a * a) % n);
+			}
+		}
+	}
+}
@@ -0,0 +1,183 @@
+// Copyright 2015 Michael 'ExpHP' Lamparski
+//
+// Licensed under the terms of the MIT License, available at:
+//  http://opensource.org/licenses/MIT
+// and also included in the file COPYING at the root of this distribution.
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+
+//! Factorization over general Euclidean / unique-factorization domains.
+//!
+//! Every `Factorizer<T>` today is hard-wired to `num::Integer` and rational
+//! integers.  This module is the abstraction layer that lets the same kind of
+//! algorithm (trial division, Pollard-Brent, ...) run over any Euclidean domain
+//! with unique factorization, with Gaussian integers (`gaussian::GaussianInt`) as
+//! the first concrete example.
+//!
+//! The trait is split in two tiers, mirroring the usual ring/domain hierarchy:
+//!
+//! - `EuclideanDomain`: has a norm and a division algorithm (`div_rem`), which is
+//!   all that's needed to run Euclid's algorithm for `gcd`.
+//! - `UfdElement`: builds on that with the extra structure (`is_unit`,
+//!   `unit_part`, `normalize`) needed to state unique factorization *up to units*.
+//!   In `Z[i]`, for instance, `1+i`, `-1-i`, `i-1` and `1-i` are all associates of
+//!   one another, and a canonical "normalized" representative has to be chosen
+//!   for `UfdFactorization` to be well defined.
+//!
+//! Future domains (`Z[omega]`, polynomial rings over a field, ...) need only
+//! implement these two traits to become factorizable; `trial_divide` below is
+//! the trait-based building block such a rewrite would share. This module does
+//! *not* itself reimplement `TrialDivisionFactorizer` or `PollardBrentFactorizer`
+//! against the trait -- those stay hard-wired to `num::Integer` in
+//! `factorizer.rs` / `factorizer_pollard.rs` -- it only supplies the piece that's
+//! domain-agnostic. `gaussian::factorize_gaussian` is what assembles
+//! `trial_divide` with real candidate-prime generation into an end-to-end
+//! factorizer for `Z[i]`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Mul;
+
+/// A Euclidean domain: has a norm compatible with a division algorithm, which is
+/// enough on its own to run Euclid's algorithm for `gcd`.
+pub trait EuclideanDomain: Sized + Clone + Eq {
+	/// A measure of "size" compatible with division: for nonzero `a`, `b`,
+	/// `norm(a) <= norm(a*b)`.
+	fn norm(self: &Self) -> u64;
+
+	/// The additive identity.
+	fn zero() -> Self;
+
+	/// The multiplicative identity.
+	fn one() -> Self;
+
+	/// True iff `self` is the additive identity.
+	fn is_zero(self: &Self) -> bool { *self == Self::zero() }
+
+	/// Euclidean division: returns `(q, r)` such that `self == q*other + r`, where
+	/// either `r` is zero or `norm(r) < norm(other)`.
+	fn div_rem(self: &Self, other: &Self) -> (Self, Self);
+
+	/// Greatest common divisor, via the Euclidean algorithm.
+	fn gcd(self: &Self, other: &Self) -> Self {
+		let mut a = self.clone();
+		let mut b = other.clone();
+		while !b.is_zero() {
+			let (_, r) = a.div_rem(&b);
+			a = b;
+			b = r;
+		}
+		a
+	}
+}
+
+/// A unique-factorization domain: a `EuclideanDomain` with enough extra
+/// structure (units, associates) to state unique factorization precisely.
+pub trait UfdElement: EuclideanDomain + Mul<Output=Self> {
+	/// True iff `self` divides `1` (in `Z`: `+-1`; in `Z[i]`: `+-1, +-i`).
+	fn is_unit(self: &Self) -> bool;
+
+	/// The unit `u` such that `self == u * self.normalize()`.
+	fn unit_part(self: &Self) -> Self;
+
+	/// The canonical representative of `self`'s associate class
+	/// (`self` divided by its own `unit_part`), chosen so that every element has
+	/// exactly one normalized associate.
+	fn normalize(self: &Self) -> Self;
+}
+
+/// The factorization of a `UfdElement`: a unit together with a multiset of
+/// normalized primes and their exponents, mirroring `Factorization<T>` but
+/// carrying the extra unit factor that's needed once associates exist.
+pub struct UfdFactorization<U: UfdElement> {
+	unit:   U,
+	powers: HashMap<U, usize>,
+}
+
+impl<U> UfdFactorization<U>
+ where U: UfdElement + Eq + Hash
+{
+	/// The factorization of `1`: unit `1`, no prime factors.
+	pub fn one() -> Self
+	{
+		UfdFactorization { unit: UfdElement::one(), powers: HashMap::new() }
+	}
+
+	pub fn unit(self: &Self) -> &U { &self.unit }
+
+	/// Multiplies the running unit factor by `u` (itself expected to be a unit).
+	pub fn mul_unit(self: &mut Self, u: U)
+	{
+		self.unit = self.unit.clone() * u;
+	}
+
+	/// Exponent of the normalized prime `p` in this factorization (`0` if absent).
+	pub fn get(self: &Self, p: &U) -> usize
+	{
+		*self.powers.get(&p.normalize()).unwrap_or(&0)
+	}
+
+	/// Sets the exponent of the normalized prime `p`.
+	pub fn set(self: &mut Self, p: U, exponent: usize)
+	{
+		self.powers.insert(p.normalize(), exponent);
+	}
+
+	/// Reconstructs the original element exactly, recorded unit factor included.
+	pub fn product(self: &Self) -> U
+	{
+		let mut result = self.unit.clone();
+		for (p, &exponent) in self.powers.iter() {
+			for _ in (0usize..exponent) {
+				result = result * p.clone();
+			}
+		}
+		result
+	}
+}
+
+/// Trial division generic over any `UfdElement`: repeatedly divides `n` by each
+/// of `candidate_primes` (in order), recording the unit factor picked up along
+/// the way so that `product()` reconstructs `n` exactly -- including, if
+/// `candidate_primes` doesn't fully factor `n`, recording whatever non-unit
+/// cofactor is left over as a single (possibly composite) factor, rather than
+/// silently dropping it.
+///
+/// This is the trait-based building block a `Factorizer` rewritten against
+/// `UfdElement` would share, regardless of domain.
+pub fn trial_divide<U>(n: U, candidate_primes: &[U]) -> UfdFactorization<U>
+ where U: UfdElement + Eq + Hash
+{
+	assert!(!n.is_zero());
+
+	let mut result = UfdFactorization::one();
+	result.mul_unit(n.unit_part());
+
+	let mut remaining = n.normalize();
+	for p in candidate_primes.iter() {
+		let mut count = 0usize;
+		while !remaining.is_zero() {
+			let (q, r) = remaining.div_rem(p);
+			if !r.is_zero() { break; }
+			// q isn't guaranteed to already be normalized (normalized / normalized
+			//  need not land back on a normalized associate), so re-normalize it
+			//  and fold its unit part into the running total, same as `n` above.
+			result.mul_unit(q.unit_part());
+			remaining = q.normalize();
+			count += 1;
+		}
+		if count > 0 {
+			result.set(p.clone(), count);
+		}
+	}
+
+	// Anything left over that isn't a unit is a cofactor `candidate_primes`
+	//  didn't cover; record it directly (as its own factor, exponent 1) so that
+	//  `product()` still reconstructs `n` exactly instead of quietly losing it.
+	if !remaining.is_unit() {
+		let existing = result.get(&remaining);
+		result.set(remaining.clone(), existing + 1);
+	}
+
+	result
+}
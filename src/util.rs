@@ -0,0 +1,206 @@
+// Copyright 2015 Michael 'ExpHP' Lamparski
+//
+// Licensed under the terms of the MIT License, available at:
+//  http://opensource.org/licenses/MIT
+// and also included in the file COPYING at the root of this distribution.
+// This file may not be copied, modified, or distributed except according
+// to those terms.
+
+extern crate num;
+
+use std::ops::{Shr,Rem};
+
+use num::{Zero,One,Integer,FromPrimitive,ToPrimitive};
+
+/// Converts an integer literal to any numeric type supported by `num`.
+///
+/// Mostly exists to keep call sites (`literal(2)`, `literal(3)`, ...) readable in
+/// generic code where the target type can't be inferred from the literal alone.
+pub fn literal<T: FromPrimitive>(n: i64) -> T
+{
+	FromPrimitive::from_i64(n).expect("literal out of range for T")
+}
+
+/// Greatest common divisor, via the standard Euclidean algorithm.
+pub fn gcd<T>(mut a: T, mut b: T) -> T
+ where T: Clone + Zero + Integer
+{
+	while !b.is_zero() {
+		let r = a % b.clone();
+		a = b;
+		b = r;
+	}
+	a
+}
+
+/// Largest `r` such that `r * r <= n` (i.e. `floor(sqrt(n))`).
+pub fn isqrt<T>(n: T) -> T
+ where T: Clone + Zero + One + Integer + Shr<usize, Output=T>
+{
+	nth_root(n, 2)
+}
+
+/// Largest `r` such that `r * r * .. * r (k times) <= n` (i.e. `floor(n^(1/k))`),
+/// computed by integer Newton iteration.
+///
+/// `k` must be at least 1; `nth_root(n, 1) == n` and `nth_root(n, k)` for `n < 2`
+/// returns `n` directly, since `0` and `1` are their own roots of every order.
+pub fn nth_root<T>(n: T, k: usize) -> T
+ where T: Clone + Zero + One + Integer + Shr<usize, Output=T>
+{
+	assert!(k >= 1);
+
+	if k == 1 || n < literal_of(2, &n) { return n; }
+
+	// Seed with a power-of-two upper bound: x = 2^(ceil(bits(n)/k)).
+	let bits = bit_length(n.clone());
+	let shift = (bits + k - 1) / k;
+	let mut x: T = One::one();
+	for _ in (0usize..shift) { x = x + x.clone(); }
+
+	// Newton iteration on f(x) = x^k - n:
+	//   x <- ((k-1)*x + n / x^(k-1)) / k
+	// monotonically decreasing once x overshoots the true root; stop as soon as
+	// it stops decreasing and then fix off-by-one errors by direct comparison.
+	loop {
+		let x_pow_km1 = pow(x.clone(), k - 1);
+		let k_t = literal_of(k as i64, &n);
+		let next = (literal_of((k - 1) as i64, &n) * x.clone() + n.clone() / x_pow_km1) / k_t;
+
+		if next >= x { break; }
+		x = next;
+	}
+
+	// x now either is, or slightly overshoots, the true root; nudge down then
+	// check whether bumping up by one still stays within bounds.
+	while pow(x.clone(), k) > n { x = x - One::one(); }
+	while pow(x.clone() + One::one(), k) <= n { x = x + One::one(); }
+
+	x
+}
+
+/// Like `nth_root`, but also reports whether the root is exact (`x^k == n`).
+pub fn nth_root_exact<T>(n: T, k: usize) -> (T, bool)
+ where T: Clone + Zero + One + Integer + Shr<usize, Output=T>
+{
+	let root = nth_root(n.clone(), k);
+	let is_exact = pow(root.clone(), k) == n;
+	(root, is_exact)
+}
+
+/// If `n` is a perfect power (`n == base^exp` for some `exp >= 2`), returns the
+/// pair with the largest such `exp` (and correspondingly smallest `base`): the
+/// `exp` returned is the gcd of all exponents in `n`'s prime factorization.
+///
+/// Only prime exponents are tried at each step; a composite exponent like 6 is
+/// caught by repeatedly re-applying this to the result (its prime factors 2 and
+/// 3 each reduce further), rather than by trying 6 directly.
+pub fn perfect_power<T>(n: T) -> Option<(T, usize)>
+ where T: Clone + Zero + One + Integer + Shr<usize, Output=T> + ToPrimitive
+{
+	if n <= One::one() { return None; }
+
+	let mut base = n;
+	let mut exp = 1usize;
+
+	// Keep peeling off any valid prime-root reduction and re-checking the
+	//  (smaller) result, so that e.g. 4096 = 64^2 = 8^4 = 2^12 settles on the
+	//  maximal exponent 12 rather than stopping at the first prime root found.
+	loop {
+		let max_k = bit_length(base.clone());
+		let mut reduced = None;
+
+		for k in primes_upto_usize(max_k) {
+			let (root, is_exact) = nth_root_exact(base.clone(), k);
+			if is_exact && root > One::one() {
+				reduced = Some((root, k));
+				break;
+			}
+		}
+
+		match reduced {
+			Some((root, k)) => { base = root; exp = exp * k; }
+			None => break,
+		}
+	}
+
+	if exp > 1 { Some((base, exp)) } else { None }
+}
+
+// Number of bits needed to represent n (n > 0), i.e. floor(log2(n)) + 1.
+fn bit_length<T>(mut n: T) -> usize
+ where T: Clone + Zero + One + Shr<usize, Output=T> + PartialOrd
+{
+	let mut bits = 0usize;
+	while n > Zero::zero() {
+		n = n >> 1usize;
+		bits += 1;
+	}
+	bits
+}
+
+// x^k via repeated multiplication (k is always small: a bit-length at most).
+fn pow<T>(x: T, k: usize) -> T
+ where T: Clone + One
+{
+	let mut result: T = One::one();
+	for _ in (0usize..k) { result = result * x.clone(); }
+	result
+}
+
+// A literal, inferred to be the same type as `sample` (used since `FromPrimitive`
+// alone isn't always enough for type inference at call sites deep in generic code).
+fn literal_of<T: FromPrimitive>(n: i64, _sample: &T) -> T
+{
+	literal(n)
+}
+
+// Prime numbers up to and including `limit`, via trial division (`limit` here is
+// always a small bit-length, so this need not be fast).
+fn primes_upto_usize(limit: usize) -> Vec<usize>
+{
+	(2usize..(limit+1)).filter(|&k| (2usize..k).all(|d| k % d != 0)).collect()
+}
+
+/// Modular exponentiation: `base^exp (mod modulus)`, via square-and-multiply.
+pub fn mod_pow<T>(mut base: T, mut exp: T, modulus: T) -> T
+ where T: Clone + Zero + One + Integer
+{
+	let mut result: T = One::one();
+	base = base % modulus.clone();
+
+	while !exp.is_zero() {
+		if exp.is_odd() {
+			result = result * base.clone() % modulus.clone();
+		}
+		exp = exp / literal_of(2, &exp);
+		base = base.clone() * base % modulus.clone();
+	}
+	result
+}
+
+#[test]
+fn test_nth_root() {
+	assert_eq!(nth_root(0usize, 2), 0);
+	assert_eq!(nth_root(1usize, 5), 1);
+	assert_eq!(nth_root(8usize, 3), 2);
+	assert_eq!(nth_root(9usize, 3), 2);  // not exact: floor(9^(1/3)) == 2
+	assert_eq!(nth_root(4096usize, 12), 2);
+	assert_eq!(nth_root(99usize, 2), 9); // floor(sqrt(99)) == 9, not 10
+}
+
+#[test]
+fn test_nth_root_exact() {
+	assert_eq!(nth_root_exact(8usize, 3), (2, true));
+	assert_eq!(nth_root_exact(9usize, 3), (2, false));
+}
+
+#[test]
+fn test_perfect_power() {
+	assert_eq!(perfect_power(2usize), None);
+	assert_eq!(perfect_power(6usize), None);  // not a perfect power at all
+	assert_eq!(perfect_power(8usize), Some((2, 3)));
+	assert_eq!(perfect_power(64usize), Some((2, 6)));
+	assert_eq!(perfect_power(4096usize), Some((2, 12))); // largest exp, not (64, 2)
+	assert_eq!(perfect_power(100usize), Some((10, 2)));
+}